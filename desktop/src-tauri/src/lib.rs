@@ -1,21 +1,22 @@
 use tauri::{
     image::Image,
-    menu::{Menu, MenuItem, PredefinedMenuItem},
+    menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem},
     tray::{MouseButton, TrayIconBuilder, TrayIconEvent},
     Manager, Runtime, Resource,
 };
 use image::GenericImageView;
+use serde::{Deserialize, Serialize};
 use std::process::{Command, Stdio};
 use std::thread;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
-use std::io::Cursor;
+use std::io::{BufRead, BufReader, Cursor};
 use std::path::PathBuf;
 use tauri::{
     menu::{
         MenuEvent,
     },
-    AppHandle, PhysicalPosition, WebviewWindow, LogicalPosition, LogicalSize,
+    AppHandle, Emitter, Listener, PhysicalPosition, WebviewWindow, LogicalPosition, LogicalSize,
 };
 
 #[cfg(target_os = "macos")]
@@ -27,7 +28,225 @@ use {
 // Global icon cache to prevent excessive loading
 lazy_static::lazy_static! {
     static ref ICON_CACHE: Arc<Mutex<HashMap<String, Image<'static>>>> = Arc::new(Mutex::new(HashMap::new()));
-    static ref CURRENT_TRAY_STATE: Arc<Mutex<String>> = Arc::new(Mutex::new("ready".to_string()));
+}
+
+/// Centralized, managed application state. Replaces the old standalone
+/// `CURRENT_TRAY_STATE` global — this is the single source of truth for the
+/// tray's current state, and every transition is broadcast via `emit` rather
+/// than polled or pushed into the webview via `eval`.
+struct AppState {
+    tray_state: Mutex<String>,
+    /// Most recently recognized gesture names, newest first, capped at
+    /// `MAX_RECENT_GESTURES`, surfaced in the tray/app menu's "Recently
+    /// recognized" submenu.
+    recent_gestures: Mutex<Vec<String>>,
+    /// Whether gesture recognition is currently paused via the menu toggle.
+    pause_recognition: std::sync::atomic::AtomicBool,
+    /// Mirrors the main window's real visibility, kept in lockstep by every
+    /// `hide_window`/`show_window` call so the "Show/Hide AirCut" menu item
+    /// never disagrees with what's actually on screen.
+    window_visible: std::sync::atomic::AtomicBool,
+    /// Persisted menu toggles, loaded from `ui-preferences.json` on startup.
+    launch_at_login: std::sync::atomic::AtomicBool,
+    show_on_all_workspaces: std::sync::atomic::AtomicBool,
+}
+
+impl AppState {
+    fn new() -> Self {
+        Self {
+            tray_state: Mutex::new("ready".to_string()),
+            recent_gestures: Mutex::new(Vec::new()),
+            pause_recognition: std::sync::atomic::AtomicBool::new(false),
+            window_visible: std::sync::atomic::AtomicBool::new(true),
+            launch_at_login: std::sync::atomic::AtomicBool::new(false),
+            show_on_all_workspaces: std::sync::atomic::AtomicBool::new(true),
+        }
+    }
+}
+
+/// Menu toggles the user controls from the tray/app menu, persisted to disk
+/// so they survive restarts.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct UiPreferences {
+    launch_at_login: bool,
+    show_on_all_workspaces: bool,
+}
+
+impl Default for UiPreferences {
+    fn default() -> Self {
+        Self {
+            launch_at_login: false,
+            show_on_all_workspaces: true,
+        }
+    }
+}
+
+fn ui_preferences_path(app: &tauri::AppHandle) -> PathBuf {
+    let dir = app.path().app_config_dir().unwrap_or_default();
+    dir.join("ui-preferences.json")
+}
+
+fn load_ui_preferences(app: &tauri::AppHandle) -> UiPreferences {
+    std::fs::read_to_string(ui_preferences_path(app))
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn save_ui_preferences(app: &tauri::AppHandle, prefs: &UiPreferences) {
+    let path = ui_preferences_path(app);
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(prefs) {
+        let _ = std::fs::write(path, json);
+    }
+}
+
+/// Snapshot the current `AppState` toggles and write them to disk — called
+/// after every menu-driven preference change.
+fn persist_ui_preferences(app: &tauri::AppHandle) {
+    let state = app.state::<AppState>();
+    let prefs = UiPreferences {
+        launch_at_login: state.launch_at_login.load(std::sync::atomic::Ordering::SeqCst),
+        show_on_all_workspaces: state.show_on_all_workspaces.load(std::sync::atomic::Ordering::SeqCst),
+    };
+    save_ui_preferences(app, &prefs);
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct TrayStateChangedPayload {
+    state: String,
+}
+
+/// Emit the `window-shown` event so the frontend can react via the real
+/// event API instead of a synthetic DOM event pushed in through `eval`.
+fn emit_window_shown(app: &tauri::AppHandle) {
+    if let Err(e) = app.emit("window-shown", ()) {
+        println!("⚠️ Failed to emit window-shown: {}", e);
+    }
+}
+
+/// Number of pre-rendered frames in the `tray-recognizing-{theme}-N.png` sequence.
+const RECOGNIZING_FRAME_COUNT: usize = 8;
+const RECOGNIZING_FRAME_INTERVAL_MS: u64 = 80;
+
+/// Owns the live `TrayIcon` handle plus the animation loop's bookkeeping, so the
+/// "recognizing" spinner has exactly one place that knows which frame it's on.
+struct TrayController {
+    tray: Mutex<Option<tauri::tray::TrayIcon<tauri::Wry>>>,
+    frame_index: Mutex<usize>,
+    is_animating: std::sync::atomic::AtomicBool,
+}
+
+impl TrayController {
+    fn new() -> Self {
+        Self {
+            tray: Mutex::new(None),
+            frame_index: Mutex::new(0),
+            is_animating: std::sync::atomic::AtomicBool::new(false),
+        }
+    }
+
+    fn set_tray(&self, tray: tauri::tray::TrayIcon<tauri::Wry>) {
+        *self.tray.lock().unwrap() = Some(tray);
+    }
+}
+
+/// Set the static tooltip + icon for `state` on the main tray, same lookup the
+/// animation loop falls back to once it exits.
+fn apply_static_tray_icon(app: &tauri::AppHandle, state: &str) -> Result<(), String> {
+    let tooltip = match state {
+        "ready" => "AirCut - Ready to detect gestures",
+        "drawing" => "AirCut - Recording gesture...",
+        "recognizing" => "AirCut - Recognizing gesture...",
+        "recognized" => "AirCut - Gesture recognized",
+        "not_recognized" => "AirCut - Gesture not recognized",
+        "disconnected" => "AirCut - Disconnected from backend",
+        _ => "AirCut",
+    };
+
+    let Some(tray) = app.tray_by_id("main") else {
+        println!("⚠️ Tray icon not found, attempting to recreate it");
+        return recreate_tray_icon(app);
+    };
+
+    tray.set_tooltip(Some(tooltip)).map_err(|e| e.to_string())?;
+
+    let icon_path = get_icon_path(state);
+    let icon_full_path = if cfg!(debug_assertions) {
+        std::env::current_dir().map_err(|e| e.to_string())?.join(&icon_path)
+    } else {
+        app.path().resource_dir().map_err(|e| e.to_string())?.join(&icon_path)
+    };
+
+    if icon_full_path.exists() {
+        match load_icon_from_path(&icon_full_path) {
+            Ok(icon) => {
+                tray.set_icon(Some(icon)).map_err(|e| e.to_string())?;
+                println!("🎨 Updated tray icon to: {} ({})", state, icon_path);
+            }
+            Err(e) => println!("⚠️ Failed to load icon {}: {}", icon_path, e),
+        }
+    } else {
+        println!("⚠️ Icon file not found: {}", icon_full_path.display());
+    }
+
+    Ok(())
+}
+
+/// Start the "recognizing" spinner: cycles through the pre-loaded frame icons
+/// every ~80ms until the shared tray state moves on to something else, then
+/// restores whatever static icon that new state calls for. Only one of these
+/// loops ever runs at a time, guarded by `TrayController::is_animating`.
+fn start_recognizing_animation(app: tauri::AppHandle) {
+    let controller = app.state::<TrayController>();
+    if controller.is_animating.swap(true, std::sync::atomic::Ordering::SeqCst) {
+        // A loop is already running; it will pick up the "recognizing" state itself.
+        return;
+    }
+
+    thread::spawn(move || {
+        let theme = if is_dark_mode() { "dark" } else { "light" };
+        let mut frame: usize = 0;
+
+        loop {
+            {
+                let current = app.state::<AppState>().tray_state.lock().unwrap();
+                if *current != "recognizing" {
+                    break;
+                }
+            }
+
+            let icon_path = format!("icons/tray-recognizing-{}-{}.png", theme, frame % RECOGNIZING_FRAME_COUNT);
+            let icon_full_path = if cfg!(debug_assertions) {
+                std::env::current_dir().unwrap_or_default().join(&icon_path)
+            } else {
+                app.path().resource_dir().unwrap_or_default().join(&icon_path)
+            };
+
+            if icon_full_path.exists() {
+                if let Ok(icon) = load_icon_from_path(&icon_full_path) {
+                    let controller = app.state::<TrayController>();
+                    if let Some(tray) = controller.tray.lock().unwrap().as_ref() {
+                        let _ = tray.set_icon(Some(icon));
+                    }
+                    *controller.frame_index.lock().unwrap() = frame % RECOGNIZING_FRAME_COUNT;
+                }
+            }
+
+            frame = frame.wrapping_add(1);
+            thread::sleep(std::time::Duration::from_millis(RECOGNIZING_FRAME_INTERVAL_MS));
+        }
+
+        let controller = app.state::<TrayController>();
+        controller.is_animating.store(false, std::sync::atomic::Ordering::SeqCst);
+
+        let final_state = app.state::<AppState>().tray_state.lock().unwrap().clone();
+        if let Err(e) = apply_static_tray_icon(&app, &final_state) {
+            println!("⚠️ Failed to restore static tray icon after animation: {}", e);
+        }
+    });
 }
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
@@ -36,12 +255,231 @@ fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
 }
 
+/// A single named, allowlisted operation a gesture is permitted to trigger.
+///
+/// Modeled on Tauri's shell `Scopes`: the program is fixed (never resolved from
+/// caller input), `args` is a template where `{param}` placeholders are filled in
+/// from `run_action`'s `params` map, and any placeholder listed in `path_params`
+/// is additionally checked against `allow`/`deny` glob scopes before it's used.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct ActionDefinition {
+    id: String,
+    program: String,
+    #[serde(default)]
+    args: Vec<String>,
+    #[serde(default)]
+    path_params: Vec<String>,
+    #[serde(default)]
+    allow: Vec<String>,
+    #[serde(default)]
+    deny: Vec<String>,
+}
+
+/// A gesture's display name bound to the action it triggers when recognized.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct GestureBinding {
+    gesture: String,
+    action_id: String,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct ActionRegistryConfig {
+    #[serde(default)]
+    actions: Vec<ActionDefinition>,
+    #[serde(default)]
+    gestures: Vec<GestureBinding>,
+}
+
+#[derive(Debug, Default)]
+struct ActionRegistryData {
+    actions: HashMap<String, ActionDefinition>,
+    gestures: Vec<GestureBinding>,
+}
+
+/// App state holding the loaded action registry and the gesture bindings that
+/// drive both `run_action` and the tray/app menu's gesture listing.
+struct ActionRegistry(Mutex<ActionRegistryData>);
+
+fn action_registry_config_path(app: &tauri::AppHandle) -> PathBuf {
+    let dir = if cfg!(debug_assertions) {
+        std::env::current_dir().unwrap_or_default()
+    } else {
+        app.path().resource_dir().unwrap_or_default()
+    };
+    dir.join("actions.json")
+}
+
+fn load_action_registry(app: &tauri::AppHandle) -> ActionRegistryData {
+    let path = action_registry_config_path(app);
+    let config: ActionRegistryConfig = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default();
+
+    if config.actions.is_empty() {
+        println!("⚠️ No action registry found at {}, starting with an empty registry", path.display());
+    } else {
+        println!(
+            "✅ Loaded {} action(s) and {} gesture binding(s) from {}",
+            config.actions.len(),
+            config.gestures.len(),
+            path.display()
+        );
+    }
+
+    ActionRegistryData {
+        actions: config.actions.into_iter().map(|a| (a.id.clone(), a)).collect(),
+        gestures: config.gestures,
+    }
+}
+
+/// Minimal glob matcher supporting `*` (any run of characters) and `?` (single
+/// character), which is all the action scope config needs.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    fn inner(p: &[u8], t: &[u8]) -> bool {
+        match (p.first(), t.first()) {
+            (None, None) => true,
+            (Some(b'*'), _) => inner(&p[1..], t) || (!t.is_empty() && inner(p, &t[1..])),
+            (Some(b'?'), Some(_)) => inner(&p[1..], &t[1..]),
+            (Some(pc), Some(tc)) if pc == tc => inner(&p[1..], &t[1..]),
+            _ => false,
+        }
+    }
+    inner(pattern.as_bytes(), text.as_bytes())
+}
+
+/// Lexically resolve `.`/`..` components without touching the filesystem (so
+/// it also works for paths that don't exist yet), rejecting any value whose
+/// `..` would walk it above its own root. Matching an un-canonicalized value
+/// against an allow glob lets `*` match straight through `..`/`/`, so this
+/// runs before every scope check — the same class of normalization Tauri's
+/// `ShellScope`/path `Scopes` perform before matching.
+fn normalize_path_components(value: &str) -> Option<String> {
+    let is_absolute = value.starts_with('/');
+    let mut stack: Vec<&str> = Vec::new();
+    for component in value.split('/') {
+        match component {
+            "" | "." => continue,
+            ".." => {
+                if stack.pop().is_none() {
+                    return None;
+                }
+            }
+            other => stack.push(other),
+        }
+    }
+    let joined = stack.join("/");
+    Some(if is_absolute { format!("/{}", joined) } else { joined })
+}
+
+/// Validate `value` against `action`'s path-param scope, returning the
+/// lexically-normalized value to substitute into the command — callers must
+/// use this normalized value, not the raw one, so a value that merely
+/// resolves within the allow root (e.g. `/safe/x/../y`) can't hand the
+/// spawned process a literal `..` to reinterpret differently.
+fn param_allowed(action: &ActionDefinition, param_name: &str, value: &str) -> Result<String, String> {
+    if !action.path_params.iter().any(|p| p == param_name) {
+        return Ok(value.to_string());
+    }
+
+    let Some(normalized) = normalize_path_components(value) else {
+        return Err(format!("param '{}' value '{}' escapes its root via '..'", param_name, value));
+    };
+
+    if action.deny.iter().any(|pattern| glob_match(pattern, &normalized)) {
+        return Err(format!("param '{}' value '{}' is denied by scope", param_name, value));
+    }
+
+    // Fail closed: a path param with no `allow` patterns has no scope at all,
+    // not an unrestricted one. Actions that truly want to accept anything
+    // must say so explicitly with `allow: ["*"]`.
+    if !action.allow.iter().any(|pattern| glob_match(pattern, &normalized)) {
+        return Err(format!("param '{}' value '{}' is not in the allow scope", param_name, value));
+    }
+
+    Ok(normalized)
+}
+
+/// Resolve `action_id` against the registry, validate `params` against its
+/// scope, and spawn the fixed program with args passed as a vector — never
+/// through a shell. This is the only way a recognized gesture can run a
+/// process; there is no path from user/gesture input to a shell string.
+///
+/// Synchronous so the tray/app menu's gesture items (no async executor handy
+/// in a `muda` click callback) can call it from a plain thread, same as
+/// `run_action` does from the async command.
+fn run_action_sync(app: &tauri::AppHandle, action_id: &str, params: &HashMap<String, String>) -> Result<String, String> {
+    let action = {
+        let registry = app.state::<ActionRegistry>();
+        let registry = registry.0.lock().unwrap();
+        registry
+            .actions
+            .get(action_id)
+            .cloned()
+            .ok_or_else(|| format!("unknown action id: {}", action_id))?
+    };
+
+    let mut resolved_args = Vec::with_capacity(action.args.len());
+    for template in &action.args {
+        let mut resolved = template.clone();
+        for (name, value) in params {
+            let placeholder = format!("{{{}}}", name);
+            // Check the original template text, not the accumulating
+            // `resolved` string — otherwise a param whose *value* happens to
+            // contain another param's `{placeholder}` syntax gets that text
+            // spliced in by a later iteration with zero scope enforcement.
+            if template.contains(&placeholder) {
+                if value.contains('{') || value.contains('}') {
+                    return Err(format!("param '{}' value must not contain '{{' or '}}'", name));
+                }
+                let substituted = param_allowed(&action, name, value)?;
+                resolved = resolved.replace(&placeholder, &substituted);
+            }
+        }
+        resolved_args.push(resolved);
+    }
+
+    println!("🚀 Running action '{}': {} {:?}", action_id, action.program, resolved_args);
+
+    let output = Command::new(&action.program)
+        .args(&resolved_args)
+        .output()
+        .map_err(|e| format!("failed to spawn '{}': {}", action.program, e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    if output.status.success() {
+        println!("✅ Action '{}' executed successfully", action_id);
+        Ok(format!("Action executed successfully.\nOutput: {}", stdout))
+    } else {
+        println!("❌ Action '{}' failed with stderr: {}", action_id, stderr);
+        Err(format!(
+            "Action failed with exit code {}.\nError: {}",
+            output.status.code().unwrap_or(-1),
+            stderr
+        ))
+    }
+}
+
+#[tauri::command]
+async fn run_action(
+    app: tauri::AppHandle,
+    action_id: String,
+    params: HashMap<String, String>,
+) -> Result<String, String> {
+    run_action_sync(&app, &action_id, &params)
+}
+
+/// Legacy unrestricted shell execution, retained only for local debugging —
+/// superseded by [`run_action`], which is the only command gestures may invoke.
+#[cfg(debug_assertions)]
 #[tauri::command]
 async fn execute_command(command: String) -> Result<String, String> {
     use std::process::Command;
-    
+
     println!("🚀 Executing command: {}", command);
-    
+
     let output = if cfg!(target_os = "windows") {
         Command::new("cmd")
             .args(["/C", &command])
@@ -51,18 +489,18 @@ async fn execute_command(command: String) -> Result<String, String> {
             .args(["-c", &command])
             .output()
     };
-    
+
     match output {
         Ok(output) => {
             let stdout = String::from_utf8_lossy(&output.stdout);
             let stderr = String::from_utf8_lossy(&output.stderr);
-            
+
             if output.status.success() {
                 println!("✅ Command executed successfully");
                 Ok(format!("Command executed successfully.\nOutput: {}", stdout))
             } else {
                 println!("❌ Command failed with stderr: {}", stderr);
-                Err(format!("Command failed with exit code {}.\nError: {}", 
+                Err(format!("Command failed with exit code {}.\nError: {}",
                     output.status.code().unwrap_or(-1), stderr))
             }
         }
@@ -137,39 +575,52 @@ fn load_icon_from_path(path: &std::path::Path) -> Result<Image<'static>, Box<dyn
     Ok(icon)
 }
 
-#[tauri::command]
-async fn update_tray_icon(app: tauri::AppHandle, state: String) -> Result<(), String> {
+/// Core tray-state transition logic, synchronous so it can be driven both from
+/// the `update_tray_icon` command and from background threads (auto-transition
+/// timers, the backend supervisor) that have no async executor to hand.
+fn set_tray_state(app: tauri::AppHandle, state: String) -> Result<(), String> {
     // Get current state and check if we should update
-    let mut current_state = CURRENT_TRAY_STATE.lock().unwrap();
+    let app_state = app.state::<AppState>();
+    let mut current_state = app_state.tray_state.lock().unwrap();
     
     // Don't update if we're already in a higher priority state
     // Priority: drawing > recognizing > recognized > not_recognized > ready > disconnected
-    let should_update = match current_state.as_str() {
-        "drawing" => {
-            // Only update if we're transitioning to "recognizing" or keeping "drawing"
-            state == "recognizing" || state == "drawing"
-        },
-        "recognizing" => {
-            // Only update if we're transitioning to "recognized", "not_recognized", or back to "drawing"
-            state == "recognized" || state == "not_recognized" || state == "drawing"
-        },
-        "recognized" => {
-            // Allow transition to any state except "ready" or "disconnected"
-            state != "ready" && state != "disconnected" || state == "drawing"
-        },
-        "not_recognized" => {
-            // Allow transition to any state except "ready" or "disconnected"
-            state != "ready" && state != "disconnected" || state == "drawing"
-        },
-        "ready" => {
-            // Always allow transitions from ready state
-            true
-        },
-        "disconnected" => {
-            // Always allow transitions from disconnected state
-            true
-        },
-        _ => true,
+    //
+    // Backend connectivity always wins over gesture-recognition priority: the
+    // supervisor's "disconnected" (backend unreachable) and the matching
+    // reconnect back to "ready" must never be swallowed, or the tray gets
+    // stuck showing a stale gesture state (or a "recognizing" animation that
+    // never exits) while the backend is actually down.
+    let should_update = if state == "disconnected" || (state == "ready" && *current_state == "disconnected") {
+        true
+    } else {
+        match current_state.as_str() {
+            "drawing" => {
+                // Only update if we're transitioning to "recognizing" or keeping "drawing"
+                state == "recognizing" || state == "drawing"
+            },
+            "recognizing" => {
+                // Only update if we're transitioning to "recognized", "not_recognized", or back to "drawing"
+                state == "recognized" || state == "not_recognized" || state == "drawing"
+            },
+            "recognized" => {
+                // Allow transition to any state except "ready" or "disconnected"
+                state != "ready" && state != "disconnected" || state == "drawing"
+            },
+            "not_recognized" => {
+                // Allow transition to any state except "ready" or "disconnected"
+                state != "ready" && state != "disconnected" || state == "drawing"
+            },
+            "ready" => {
+                // Always allow transitions from ready state
+                true
+            },
+            "disconnected" => {
+                // Always allow transitions from disconnected state
+                true
+            },
+            _ => true,
+        }
     };
     
     if !should_update {
@@ -217,9 +668,10 @@ async fn update_tray_icon(app: tauri::AppHandle, state: String) -> Result<(), St
                         println!("🎨 Auto-updated tray icon to: ready ({})", icon_path);
                         
                         // Update the state tracking
-                        if let Ok(mut state) = CURRENT_TRAY_STATE.lock() {
+                        if let Ok(mut state) = app_handle.state::<AppState>().tray_state.lock() {
                             *state = "ready".to_string();
                         }
+                        let _ = app_handle.emit("tray-state-changed", TrayStateChangedPayload { state: "ready".to_string() });
                     }
                 } else {
                     println!("⚠️ Tray icon not found during auto-transition, attempting to recreate");
@@ -266,9 +718,10 @@ async fn update_tray_icon(app: tauri::AppHandle, state: String) -> Result<(), St
                         println!("🎨 Auto-updated tray icon to: ready ({})", icon_path);
                         
                         // Update the state tracking
-                        if let Ok(mut state) = CURRENT_TRAY_STATE.lock() {
+                        if let Ok(mut state) = app_handle.state::<AppState>().tray_state.lock() {
                             *state = "ready".to_string();
                         }
+                        let _ = app_handle.emit("tray-state-changed", TrayStateChangedPayload { state: "ready".to_string() });
                     }
                 } else {
                     println!("⚠️ Tray icon not found during auto-transition, attempting to recreate");
@@ -282,60 +735,133 @@ async fn update_tray_icon(app: tauri::AppHandle, state: String) -> Result<(), St
     
     // Update the current state
     *current_state = state.clone();
+    drop(current_state);
     println!("🔄 Tray icon state changed to: {}", state);
-    
-    let tooltip = match state.as_str() {
-        "ready" => "AirCut - Ready to detect gestures",
-        "drawing" => "AirCut - Recording gesture...",
-        "recognizing" => "AirCut - Recognizing gesture...",
-        "recognized" => "AirCut - Gesture recognized",
-        "not_recognized" => "AirCut - Gesture not recognized",
-        "disconnected" => "AirCut - Disconnected from backend",
-        _ => "AirCut",
-    };
-    
-    if let Some(tray) = app.tray_by_id("main") {
-        // Update tooltip
-        tray.set_tooltip(Some(tooltip)).map_err(|e| e.to_string())?;
-        
-        // Update icon
-        let icon_path = get_icon_path(&state);
-        
-        // In development mode, use the source directory; in production, use resource directory
-        let icon_full_path = if cfg!(debug_assertions) {
-            // Development mode - current dir is already src-tauri/
-            std::env::current_dir()
-                .map_err(|e| e.to_string())?
-                .join(&icon_path)
-        } else {
-            // Production mode - use resource directory
-            app.path().resource_dir()
-                .map_err(|e| e.to_string())?
-                .join(&icon_path)
-        };
-            
-        if icon_full_path.exists() {
-            match load_icon_from_path(&icon_full_path) {
-                Ok(icon) => {
-                    tray.set_icon(Some(icon)).map_err(|e| e.to_string())?;
-                    println!("🎨 Updated tray icon to: {} ({})", state, icon_path);
-                }
-                Err(e) => {
-                    println!("⚠️ Failed to load icon {}: {}", icon_path, e);
-                }
-            }
-        } else {
-            println!("⚠️ Icon file not found: {}", icon_full_path.display());
+
+    let _ = app.emit("tray-state-changed", TrayStateChangedPayload { state: state.clone() });
+
+    if state == "recognizing" {
+        // The animation loop owns the icon from here; just set the tooltip now
+        // and let it keep ticking until the state moves on.
+        if let Some(tray) = app.tray_by_id("main") {
+            let _ = tray.set_tooltip(Some("AirCut - Recognizing gesture..."));
         }
+        start_recognizing_animation(app.clone());
     } else {
-        println!("⚠️ Tray icon not found, attempting to recreate it");
-        // Try to recreate the tray icon
-        recreate_tray_icon(&app)?;
+        apply_static_tray_icon(&app, &state)?;
     }
-    
+
     Ok(())
 }
 
+#[tauri::command]
+async fn update_tray_icon(app: tauri::AppHandle, state: String) -> Result<(), String> {
+    set_tray_state(app, state)
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+struct WindowGeometry {
+    width: u32,
+    height: u32,
+    x: i32,
+    y: i32,
+}
+
+fn window_geometry_path(app: &tauri::AppHandle) -> PathBuf {
+    let dir = app.path().app_config_dir().unwrap_or_default();
+    dir.join("window-geometry.json")
+}
+
+fn load_window_geometry(app: &tauri::AppHandle) -> Option<WindowGeometry> {
+    let contents = std::fs::read_to_string(window_geometry_path(app)).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+fn save_window_geometry(app: &tauri::AppHandle, window: &WebviewWindow) {
+    let (Ok(size), Ok(position)) = (window.outer_size(), window.outer_position()) else {
+        return;
+    };
+
+    let geometry = WindowGeometry {
+        width: size.width,
+        height: size.height,
+        x: position.x,
+        y: position.y,
+    };
+
+    let path = window_geometry_path(app);
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(&geometry) {
+        let _ = std::fs::write(path, json);
+    }
+}
+
+/// Genuinely hide the main window, remembering its size/position so
+/// `show_window` can restore exactly where the user left it, instead of the
+/// old approach of shrinking it to 1x1 and teleporting it off-screen.
+fn hide_window(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        save_window_geometry(app, &window);
+        let _ = window.hide();
+        println!("📱 Window hidden");
+        set_window_visible_state(app, false);
+    }
+}
+
+/// Restore the window's last known size/position (or a sensible default on
+/// first run) and genuinely show it, the single place every show path
+/// (tray click, settings menu, dock reopen, global shortcut) routes through.
+fn show_window(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        match load_window_geometry(app) {
+            Some(geometry) => {
+                let _ = window.set_size(tauri::PhysicalSize::new(geometry.width, geometry.height));
+                let _ = window.set_position(tauri::PhysicalPosition::new(geometry.x, geometry.y));
+            }
+            None => {
+                let _ = window.set_size(tauri::LogicalSize::new(1400, 1000));
+                let _ = window.center();
+            }
+        }
+
+        #[cfg(target_os = "macos")]
+        apply_all_workspaces_preference(app, &window);
+
+        let _ = window.show();
+        let _ = window.set_focus();
+        println!("📱 Window shown");
+        emit_window_shown(app);
+        set_window_visible_state(app, true);
+    }
+}
+
+const VISIBILITY_MENU_ID: &str = "toggle_visibility";
+
+fn visibility_menu_label(visible: bool) -> &'static str {
+    if visible { "Hide AirCut" } else { "Show AirCut" }
+}
+
+/// Record the window's real visibility and push the new label onto the
+/// "Show/Hide AirCut" item on both the tray menu and the app menu bar, so
+/// the menu text is always a click away from catching up with reality.
+fn set_window_visible_state(app: &tauri::AppHandle, visible: bool) {
+    app.state::<AppState>().window_visible.store(visible, std::sync::atomic::Ordering::SeqCst);
+
+    let label = visibility_menu_label(visible);
+    if let Some(menu) = app.tray_by_id("main").and_then(|t| t.menu()) {
+        if let Some(item) = menu.get(VISIBILITY_MENU_ID).and_then(|k| k.as_menuitem().cloned()) {
+            let _ = item.set_text(label);
+        }
+    }
+    if let Some(menu) = app.menu() {
+        if let Some(item) = menu.get(VISIBILITY_MENU_ID).and_then(|k| k.as_menuitem().cloned()) {
+            let _ = item.set_text(label);
+        }
+    }
+}
+
 // Function to recreate the tray icon if it disappears
 fn recreate_tray_icon(app: &tauri::AppHandle) -> Result<(), String> {
     println!("🔄 Recreating tray icon");
@@ -384,7 +910,7 @@ fn recreate_tray_icon(app: &tauri::AppHandle) -> Result<(), String> {
     };
     
     // Create system tray
-    let _tray = TrayIconBuilder::with_id("main")
+    let tray = TrayIconBuilder::with_id("main")
         .tooltip("AirCut - Ready")
         .icon(initial_icon)
         .menu(&menu)
@@ -392,34 +918,13 @@ fn recreate_tray_icon(app: &tauri::AppHandle) -> Result<(), String> {
         .on_tray_icon_event(|tray, event| {
             match event {
                 TrayIconEvent::Click { button: MouseButton::Left, .. } => {
-                    // Left click shows/hides the main window
+                    // Left click toggles the main window
                     let app = tray.app_handle();
                     if let Some(window) = app.get_webview_window("main") {
-                        // Check if window is visible (not off-screen)
-                        let is_visible = match window.outer_position() {
-                            Ok(position) => position.x > -1000 && position.y > -1000,
-                            Err(_) => false
-                        };
-                        
-                        if is_visible {
-                            // Hide the window by moving it off-screen
-                            println!("📱 Moving window off-screen");
-                            let _ = window.set_position(tauri::LogicalPosition::new(-2000, -2000));
-                            let _ = window.set_size(tauri::LogicalSize::new(1, 1));
+                        if window.is_visible().unwrap_or(false) {
+                            hide_window(app);
                         } else {
-                            // Show the window by moving it back on-screen
-                            println!("📱 Moving window back on-screen");
-                            let _ = window.set_size(tauri::LogicalSize::new(1400, 1000));
-                            let _ = window.center();
-                            
-                            // Ensure window is visible on all workspaces (all virtual desktops)
-                            #[cfg(target_os = "macos")]
-                            make_window_visible_on_all_workspaces(&window);
-                            
-                            let _ = window.set_focus();
-                            
-                            // Dispatch window-shown event
-                            let _ = window.eval("window.dispatchEvent(new Event('window-shown'))");
+                            show_window(app);
                         }
                     }
                 }
@@ -430,118 +935,315 @@ fn recreate_tray_icon(app: &tauri::AppHandle) -> Result<(), String> {
             }
         })
         .on_menu_event(|app, event| {
-            match event.id().as_ref() {
-                "settings" => {
-                    // Show the main window when settings is clicked
-                    if let Some(window) = app.get_webview_window("main") {
-                        // Move the window back on-screen
-                        let _ = window.set_size(tauri::LogicalSize::new(1400, 1000));
-                        let _ = window.center();
-                        
-                        // Ensure window is visible on all workspaces (all virtual desktops)
-                        #[cfg(target_os = "macos")]
-                        make_window_visible_on_all_workspaces(&window);
-                        
-                        let _ = window.set_focus();
-                        
-                        // Dispatch window-shown event
-                        let _ = window.eval("window.dispatchEvent(new Event('window-shown'))");
-                    }
-                }
-                "quit" => {
-                    app.exit(0);
-                }
-                _ => {}
-            }
+            handle_shared_menu_event(app, event.id().as_ref());
         })
         .build(app)
         .map_err(|e| e.to_string())?;
-    
+
+    app.state::<TrayController>().set_tray(tray);
+
     // After recreating the tray, ensure window is set to be visible on all workspaces
     if let Some(window) = app.get_webview_window("main") {
         #[cfg(target_os = "macos")]
-        make_window_visible_on_all_workspaces(&window);
+        apply_all_workspaces_preference(app, &window);
     }
-    
+
     println!("✅ Tray icon recreated successfully");
     Ok(())
 }
 
 #[tauri::command]
 async fn show_main_window(app: tauri::AppHandle) -> Result<(), String> {
-    if let Some(window) = app.get_webview_window("main") {
-        // Move the window back on-screen
-        window.set_size(tauri::LogicalSize::new(1400, 1000)).map_err(|e| e.to_string())?;
-        window.center().map_err(|e| e.to_string())?;
-        
-        // Ensure window is visible on all workspaces (all virtual desktops)
-        #[cfg(target_os = "macos")]
-        make_window_visible_on_all_workspaces(&window);
-        
-        window.set_focus().map_err(|e| e.to_string())?;
-        
-        // Dispatch window-shown event
-        let _ = window.eval("window.dispatchEvent(new Event('window-shown'))");
-    }
+    show_window(&app);
     Ok(())
 }
 
-// Function to check if the backend is running and start it if needed
-fn ensure_backend_is_running() -> Result<(), Box<dyn std::error::Error>> {
-    // Check if the backend is running by trying to connect to the port
-    let backend_running = std::net::TcpStream::connect("127.0.0.1:8000").is_ok();
-    
-    if !backend_running {
-        println!("🔄 Backend not running, attempting to start it...");
-        
-        // Get the path to the backend directory
-        let current_dir = std::env::current_dir()?;
-        let backend_dir = current_dir.parent().unwrap().join("backend");
-        
-        // Command to start the backend
-        let mut command = if cfg!(target_os = "windows") {
-            let mut cmd = Command::new("cmd");
-            cmd.args(["/C", "python", "-m", "uvicorn", "main:app", "--host", "127.0.0.1", "--port", "8000"]);
-            cmd
-        } else {
-            let mut cmd = Command::new("python3");
-            cmd.args(["-m", "uvicorn", "main:app", "--host", "127.0.0.1", "--port", "8000"]);
-            cmd
-        };
-        
-        // Set the working directory and run in the background
-        command.current_dir(&backend_dir);
-        command.stdout(Stdio::piped());
-        command.stderr(Stdio::piped());
-        
-        // Start the process
-        match command.spawn() {
-            Ok(child) => {
-                println!("✅ Backend started successfully with PID: {:?}", child.id());
-                
-                // Start a thread to monitor the backend output
+// Custom-titlebar window controls for the main window (overlay titlebar on
+// macOS, decorations(false) elsewhere).
+
+#[tauri::command]
+fn start_window_drag(window: tauri::WebviewWindow) -> Result<(), String> {
+    window.start_dragging().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn minimize_window(window: tauri::WebviewWindow) -> Result<(), String> {
+    window.minimize().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn maximize_window(window: tauri::WebviewWindow) -> Result<(), String> {
+    if window.is_maximized().map_err(|e| e.to_string())? {
+        window.unmaximize().map_err(|e| e.to_string())
+    } else {
+        window.maximize().map_err(|e| e.to_string())
+    }
+}
+
+#[tauri::command]
+fn close_window(app: tauri::AppHandle) -> Result<(), String> {
+    hide_window(&app);
+    Ok(())
+}
+
+const DEFAULT_GLOBAL_SHORTCUT: &str = "CommandOrControl+Shift+A";
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct GlobalShortcutConfig {
+    shortcut: String,
+}
+
+/// Tracks the accelerator currently registered with the OS, so rebinding
+/// knows exactly what to unregister before registering the replacement.
+struct GlobalShortcutState(Mutex<String>);
+
+fn global_shortcut_config_path(app: &tauri::AppHandle) -> PathBuf {
+    let dir = app.path().app_config_dir().unwrap_or_default();
+    dir.join("global-shortcut.json")
+}
+
+fn load_global_shortcut(app: &tauri::AppHandle) -> String {
+    std::fs::read_to_string(global_shortcut_config_path(app))
+        .ok()
+        .and_then(|contents| serde_json::from_str::<GlobalShortcutConfig>(&contents).ok())
+        .map(|config| config.shortcut)
+        .unwrap_or_else(|| DEFAULT_GLOBAL_SHORTCUT.to_string())
+}
+
+fn save_global_shortcut(app: &tauri::AppHandle, shortcut: &str) {
+    let path = global_shortcut_config_path(app);
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let config = GlobalShortcutConfig { shortcut: shortcut.to_string() };
+    if let Ok(json) = serde_json::to_string_pretty(&config) {
+        let _ = std::fs::write(path, json);
+    }
+}
+
+/// Register `shortcut` with the OS to toggle the main window through the
+/// same `hide_window`/`show_window` path as the tray click, and remember it
+/// as the currently-registered accelerator.
+fn register_global_shortcut(app: &tauri::AppHandle, shortcut: &str) -> Result<(), String> {
+    use tauri_plugin_global_shortcut::GlobalShortcutExt;
+
+    app.global_shortcut().register(shortcut).map_err(|e| e.to_string())?;
+    *app.state::<GlobalShortcutState>().0.lock().unwrap() = shortcut.to_string();
+    println!("⌨️ Registered global shortcut: {}", shortcut);
+    Ok(())
+}
+
+/// Swap the registered global shortcut for a new accelerator, persisting the
+/// choice so it survives restarts, and used by the frontend's settings UI to
+/// let the user rebind the hotkey at runtime.
+#[tauri::command]
+fn set_global_shortcut(app: tauri::AppHandle, shortcut: String) -> Result<(), String> {
+    use tauri_plugin_global_shortcut::GlobalShortcutExt;
+
+    let previous = app.state::<GlobalShortcutState>().0.lock().unwrap().clone();
+    if previous == shortcut {
+        return Ok(());
+    }
+
+    // Register the new accelerator before touching the old one: if `shortcut`
+    // is malformed or already claimed by another app, this returns early and
+    // the working hotkey (and `GlobalShortcutState`) are left untouched,
+    // rather than unregistering first and leaving the user with no hotkey at
+    // all while `GlobalShortcutState` still points at a now-unregistered
+    // value that a retry would then treat as a no-op.
+    app.global_shortcut().register(shortcut.as_str()).map_err(|e| e.to_string())?;
+
+    if !previous.is_empty() {
+        let _ = app.global_shortcut().unregister(previous.as_str());
+    }
+
+    *app.state::<GlobalShortcutState>().0.lock().unwrap() = shortcut.clone();
+    println!("⌨️ Registered global shortcut: {}", shortcut);
+    save_global_shortcut(&app, &shortcut);
+    Ok(())
+}
+
+const BACKEND_DEFAULT_PORT: u16 = 8000;
+const BACKEND_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+const BACKEND_MIN_BACKOFF: std::time::Duration = std::time::Duration::from_secs(2);
+const BACKEND_MAX_BACKOFF: std::time::Duration = std::time::Duration::from_secs(8);
+
+#[derive(Debug, Clone, Serialize)]
+struct BackendStatusPayload {
+    running: bool,
+    port: u16,
+}
+
+/// Managed state for the backend supervisor: the port it's currently bound to
+/// (may have moved if 8000 was taken), whether the last health poll succeeded,
+/// and the child process handle so the monitor thread can detect a crashed
+/// backend and restart it.
+struct BackendSupervisor {
+    port: Mutex<u16>,
+    running: std::sync::atomic::AtomicBool,
+    child: Mutex<Option<std::process::Child>>,
+}
+
+impl BackendSupervisor {
+    fn new() -> Self {
+        Self {
+            port: Mutex::new(BACKEND_DEFAULT_PORT),
+            running: std::sync::atomic::AtomicBool::new(false),
+            child: Mutex::new(None),
+        }
+    }
+
+    fn status(&self) -> BackendStatusPayload {
+        BackendStatusPayload {
+            running: self.running.load(std::sync::atomic::Ordering::SeqCst),
+            port: *self.port.lock().unwrap(),
+        }
+    }
+}
+
+fn is_port_reachable(port: u16) -> bool {
+    std::net::TcpStream::connect(("127.0.0.1", port)).is_ok()
+}
+
+/// Pick `preferred` if it's free, otherwise ask the OS for an ephemeral port.
+fn find_free_port(preferred: u16) -> u16 {
+    if std::net::TcpListener::bind(("127.0.0.1", preferred)).is_ok() {
+        return preferred;
+    }
+
+    std::net::TcpListener::bind(("127.0.0.1", 0))
+        .and_then(|listener| listener.local_addr())
+        .map(|addr| addr.port())
+        .unwrap_or(preferred)
+}
+
+fn spawn_backend_process(port: u16) -> Option<std::process::Child> {
+    let current_dir = std::env::current_dir().ok()?;
+    let backend_dir = current_dir.parent()?.join("backend");
+    let port_str = port.to_string();
+
+    let mut command = if cfg!(target_os = "windows") {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", "python", "-m", "uvicorn", "main:app", "--host", "127.0.0.1", "--port", &port_str]);
+        cmd
+    } else {
+        let mut cmd = Command::new("python3");
+        cmd.args(["-m", "uvicorn", "main:app", "--host", "127.0.0.1", "--port", &port_str]);
+        cmd
+    };
+
+    command.current_dir(&backend_dir);
+    command.stdout(Stdio::piped());
+    command.stderr(Stdio::piped());
+
+    match command.spawn() {
+        Ok(mut child) => {
+            println!("✅ Backend started with PID {:?} on port {}", child.id(), port);
+
+            // Drain stdout/stderr on their own threads. Left unread, uvicorn's
+            // combined output eventually fills the OS pipe buffer and blocks
+            // the child's `write()` — on a single-threaded event loop that
+            // wedges request handling while the listen socket can still
+            // accept connections, so `is_port_reachable` would keep reporting
+            // "healthy" on a backend that's actually frozen.
+            if let Some(stdout) = child.stdout.take() {
                 thread::spawn(move || {
-                    let _ = child.wait_with_output();
+                    for line in BufReader::new(stdout).lines().flatten() {
+                        println!("🐍 [backend] {}", line);
+                    }
                 });
-                
-                // Wait a bit for the backend to start
-                thread::sleep(std::time::Duration::from_secs(2));
-                
-                // Check if it's actually running now
-                if std::net::TcpStream::connect("127.0.0.1:8000").is_ok() {
-                    println!("✅ Backend is now running on port 8000");
-                } else {
-                    println!("❌ Backend failed to start properly");
+            }
+            if let Some(stderr) = child.stderr.take() {
+                thread::spawn(move || {
+                    for line in BufReader::new(stderr).lines().flatten() {
+                        println!("🐍 [backend] {}", line);
+                    }
+                });
+            }
+
+            Some(child)
+        }
+        Err(e) => {
+            println!("❌ Failed to start backend: {}", e);
+            None
+        }
+    }
+}
+
+/// Long-lived monitor: polls the backend port on a fixed interval (same
+/// periodic-refresh shape as the App Nap thread), restarts it with
+/// exponential backoff on failure, and drives the tray between `disconnected`
+/// and `ready` as connectivity changes.
+fn start_backend_supervisor(app: tauri::AppHandle) {
+    thread::spawn(move || {
+        let supervisor = app.state::<BackendSupervisor>();
+
+        {
+            let mut port = supervisor.port.lock().unwrap();
+            if !is_port_reachable(*port) {
+                *port = find_free_port(*port);
+            }
+        }
+
+        let mut backoff = BACKEND_MIN_BACKOFF;
+
+        loop {
+            let port = *supervisor.port.lock().unwrap();
+
+            if is_port_reachable(port) {
+                if !supervisor.running.swap(true, std::sync::atomic::Ordering::SeqCst) {
+                    println!("✅ Backend reachable on port {}", port);
+                    let _ = app.emit("backend-status", BackendStatusPayload { running: true, port });
+                    let _ = set_tray_state(app.clone(), "ready".to_string());
                 }
-            },
-            Err(e) => {
-                println!("❌ Failed to start backend: {}", e);
+                backoff = BACKEND_MIN_BACKOFF;
+                thread::sleep(BACKEND_POLL_INTERVAL);
+                continue;
+            }
+
+            if supervisor.running.swap(false, std::sync::atomic::Ordering::SeqCst) {
+                println!("⚠️ Backend unreachable on port {}", port);
+                let _ = app.emit("backend-status", BackendStatusPayload { running: false, port });
+                let _ = set_tray_state(app.clone(), "disconnected".to_string());
             }
+
+            let mut child_guard = supervisor.child.lock().unwrap();
+            if let Some(child) = child_guard.as_mut() {
+                if matches!(child.try_wait(), Ok(Some(_))) {
+                    *child_guard = None;
+                }
+            }
+
+            if child_guard.is_none() {
+                let target_port = if is_port_reachable(port) { port } else { find_free_port(port) };
+                if target_port != port {
+                    println!("🔁 Port {} unavailable, switching backend to {}", port, target_port);
+                    *supervisor.port.lock().unwrap() = target_port;
+                }
+                *child_guard = spawn_backend_process(target_port);
+            }
+            drop(child_guard);
+
+            thread::sleep(backoff);
+            backoff = std::cmp::min(backoff * 2, BACKEND_MAX_BACKOFF);
         }
-    } else {
-        println!("✅ Backend is already running on port 8000");
+    });
+}
+
+#[tauri::command]
+fn get_backend_status(app: tauri::AppHandle) -> BackendStatusPayload {
+    app.state::<BackendSupervisor>().status()
+}
+
+#[tauri::command]
+fn restart_backend(app: tauri::AppHandle) -> Result<(), String> {
+    let supervisor = app.state::<BackendSupervisor>();
+    let mut child_guard = supervisor.child.lock().unwrap();
+    if let Some(mut child) = child_guard.take() {
+        let _ = child.kill();
+        let _ = child.wait();
     }
-    
+    supervisor.running.store(false, std::sync::atomic::Ordering::SeqCst);
     Ok(())
 }
 
@@ -552,29 +1254,260 @@ async fn startup_complete(app: tauri::AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+const GESTURE_MENU_ID_PREFIX: &str = "gesture:";
+const RECENT_GESTURE_MENU_ID_PREFIX: &str = "recent-gesture:";
+const MAX_RECENT_GESTURES: usize = 5;
+
+/// The id a click handler (tray or app menu bar) needs to look up which
+/// action a gesture menu item should run.
+fn gesture_menu_id(gesture: &str) -> String {
+    format!("{}{}", GESTURE_MENU_ID_PREFIX, gesture)
+}
+
+/// Menu items generated from the gesture-action registry: one item per
+/// configured gesture (clickable to run its bound action manually), a
+/// "Recently recognized" submenu populated at runtime, and toggles for
+/// pause-recognition / launch-at-login. Shared by the tray menu and the
+/// native app menu bar so both stay in sync with the registry.
+fn build_gesture_menu_items<R: Runtime>(app: &tauri::AppHandle<R>) -> tauri::Result<Vec<Box<dyn tauri::menu::IsMenuItem<R>>>> {
+    let mut items: Vec<Box<dyn tauri::menu::IsMenuItem<R>>> = Vec::new();
+
+    let (gestures, recent) = {
+        let registry = app.state::<ActionRegistry>();
+        let registry = registry.0.lock().unwrap();
+        let recent = app.state::<AppState>().recent_gestures.lock().unwrap().clone();
+        (registry.gestures.clone(), recent)
+    };
+
+    if gestures.is_empty() {
+        items.push(Box::new(MenuItem::new(app, "No gestures configured", false, None::<&str>)?));
+    } else {
+        for binding in &gestures {
+            let label = format!("{} → {}", binding.gesture, binding.action_id);
+            items.push(Box::new(MenuItem::with_id(
+                app,
+                gesture_menu_id(&binding.gesture),
+                label,
+                true,
+                None::<&str>,
+            )?));
+        }
+    }
+
+    items.push(Box::new(PredefinedMenuItem::separator(app)?));
+
+    let recent_items: Vec<MenuItem<R>> = if recent.is_empty() {
+        vec![MenuItem::new(app, "Nothing recognized yet", false, None::<&str>)?]
+    } else {
+        recent
+            .iter()
+            .map(|gesture| {
+                MenuItem::with_id(app, format!("{}{}", RECENT_GESTURE_MENU_ID_PREFIX, gesture), gesture, true, None::<&str>)
+            })
+            .collect::<tauri::Result<Vec<_>>>()?
+    };
+    let recent_refs: Vec<&dyn tauri::menu::IsMenuItem<R>> = recent_items.iter().map(|i| i as &dyn tauri::menu::IsMenuItem<R>).collect();
+    items.push(Box::new(tauri::menu::Submenu::with_items(app, "Recently recognized", true, &recent_refs)?));
+
+    items.push(Box::new(PredefinedMenuItem::separator(app)?));
+
+    let pause_label = if app.state::<AppState>().pause_recognition.load(std::sync::atomic::Ordering::SeqCst) {
+        "Resume Recognition"
+    } else {
+        "Pause Recognition"
+    };
+    items.push(Box::new(MenuItem::with_id(app, "pause_recognition", pause_label, true, None::<&str>)?));
+
+    let state = app.state::<AppState>();
+    let launch_at_login = state.launch_at_login.load(std::sync::atomic::Ordering::SeqCst);
+    let show_on_all_workspaces = state.show_on_all_workspaces.load(std::sync::atomic::Ordering::SeqCst);
+    items.push(Box::new(CheckMenuItem::with_id(
+        app,
+        "launch_at_login",
+        "Launch at Login",
+        true,
+        launch_at_login,
+        None::<&str>,
+    )?));
+    items.push(Box::new(CheckMenuItem::with_id(
+        app,
+        "show_on_all_workspaces",
+        "Show on All Workspaces",
+        true,
+        show_on_all_workspaces,
+        None::<&str>,
+    )?));
+
+    Ok(items)
+}
+
 fn create_tray_menu<R: Runtime>(app: &tauri::AppHandle<R>) -> tauri::Result<Menu<R>> {
-    // Create main menu items
+    let visible = app.state::<AppState>().window_visible.load(std::sync::atomic::Ordering::SeqCst);
+    let toggle_visibility = MenuItem::with_id(app, VISIBILITY_MENU_ID, visibility_menu_label(visible), true, None::<&str>)?;
     let settings = MenuItem::with_id(app, "settings", "Settings", true, None::<&str>)?;
-    // let separator1 = PredefinedMenuItem::separator(app)?;
-    
-    // Create quick action items with a prefix
-    // let quick_right = MenuItem::with_id(app, "quick_right", "Desktop next", true, None::<&str>)?;
-    // let quick_left = MenuItem::with_id(app, "quick_left", "⬅️ Left Arrow", true, None::<&str>)?;
-    // let quick_spotify = MenuItem::with_id(app, "quick_spotify", "🎵 Open Spotify", true, None::<&str>)?;
-    
+    let separator1 = PredefinedMenuItem::separator(app)?;
     let separator2 = PredefinedMenuItem::separator(app)?;
-    let quit = MenuItem::with_id(app, "quit", "Quit AirCut", true, None::<&str>)?;
-    
-    // Build the complete menu
-    Menu::with_items(app, &[
-        &settings, 
-        // &separator1, 
-        // &quick_right, 
-        // &quick_left, 
-        // &quick_spotify, 
-        &separator2, 
-        &quit
-    ])
+    let quit = MenuItem::with_id(app, "quit", "Quit AirCut", true, Some("CmdOrCtrl+Q"))?;
+
+    let mut all_items: Vec<Box<dyn tauri::menu::IsMenuItem<R>>> = vec![Box::new(toggle_visibility), Box::new(settings), Box::new(separator1)];
+    all_items.extend(build_gesture_menu_items(app)?);
+    all_items.push(Box::new(separator2));
+    all_items.push(Box::new(quit));
+
+    let refs: Vec<&dyn tauri::menu::IsMenuItem<R>> = all_items.iter().map(|i| i.as_ref()).collect();
+    Menu::with_items(app, &refs)
+}
+
+/// Native app-level menu bar (the macOS menu bar / window menus on other
+/// platforms), built from the same gesture-action registry as the tray menu.
+fn create_app_menu<R: Runtime>(app: &tauri::AppHandle<R>) -> tauri::Result<Menu<R>> {
+    let visible = app.state::<AppState>().window_visible.load(std::sync::atomic::Ordering::SeqCst);
+    let toggle_visibility = MenuItem::with_id(app, VISIBILITY_MENU_ID, visibility_menu_label(visible), true, None::<&str>)?;
+    let settings = MenuItem::with_id(app, "settings", "Settings", true, None::<&str>)?;
+    let quit = PredefinedMenuItem::quit(app, Some("Quit AirCut"))?;
+
+    let mut app_items: Vec<Box<dyn tauri::menu::IsMenuItem<R>>> = vec![Box::new(toggle_visibility), Box::new(settings), Box::new(PredefinedMenuItem::separator(app)?)];
+    app_items.extend(build_gesture_menu_items(app)?);
+    app_items.push(Box::new(PredefinedMenuItem::separator(app)?));
+    app_items.push(Box::new(quit));
+
+    let app_refs: Vec<&dyn tauri::menu::IsMenuItem<R>> = app_items.iter().map(|i| i.as_ref()).collect();
+    let app_submenu = tauri::menu::Submenu::with_items(app, "AirCut", true, &app_refs)?;
+
+    Menu::with_items(app, &[&app_submenu])
+}
+
+/// Rebuild and re-attach both the tray menu and the app menu bar — called
+/// whenever the gesture-action registry or the "recently recognized" list
+/// changes, so neither menu goes stale.
+fn rebuild_menus(app: &tauri::AppHandle) -> Result<(), String> {
+    if let Some(tray) = app.tray_by_id("main") {
+        let menu = create_tray_menu(app).map_err(|e| e.to_string())?;
+        tray.set_menu(Some(menu)).map_err(|e| e.to_string())?;
+    }
+    let app_menu = create_app_menu(app).map_err(|e| e.to_string())?;
+    app.set_menu(app_menu).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Dispatch a gesture/recent-gesture menu item click: look up the bound
+/// action and run it on a background thread (menu click callbacks have no
+/// async executor to hand).
+fn handle_gesture_menu_click(app: &tauri::AppHandle, gesture: &str) {
+    let action_id = {
+        let registry = app.state::<ActionRegistry>();
+        let registry = registry.0.lock().unwrap();
+        registry
+            .gestures
+            .iter()
+            .find(|b| b.gesture == gesture)
+            .map(|b| b.action_id.clone())
+    };
+
+    let Some(action_id) = action_id else {
+        println!("⚠️ No action bound to gesture '{}'", gesture);
+        return;
+    };
+
+    let app = app.clone();
+    thread::spawn(move || match run_action_sync(&app, &action_id, &HashMap::new()) {
+        Ok(output) => println!("✅ Ran action '{}' from menu: {}", action_id, output),
+        Err(e) => println!("❌ Failed to run action '{}' from menu: {}", action_id, e),
+    });
+}
+
+/// Single dispatch point for menu item clicks, shared by the tray menu and
+/// the native app menu bar so "settings"/"quit"/gesture items behave
+/// identically no matter which menu was clicked.
+fn handle_shared_menu_event(app: &tauri::AppHandle, id: &str) {
+    if let Some(gesture) = id.strip_prefix(GESTURE_MENU_ID_PREFIX) {
+        handle_gesture_menu_click(app, gesture);
+        return;
+    }
+    if let Some(gesture) = id.strip_prefix(RECENT_GESTURE_MENU_ID_PREFIX) {
+        handle_gesture_menu_click(app, gesture);
+        return;
+    }
+
+    match id {
+        "toggle_visibility" => {
+            if let Some(window) = app.get_webview_window("main") {
+                if window.is_visible().unwrap_or(false) {
+                    hide_window(app);
+                } else {
+                    show_window(app);
+                }
+            }
+        }
+        "settings" => {
+            show_window(app);
+        }
+        "quit" => {
+            app.exit(0);
+        }
+        "pause_recognition" => {
+            let state = app.state::<AppState>();
+            let paused = !state.pause_recognition.load(std::sync::atomic::Ordering::SeqCst);
+            state.pause_recognition.store(paused, std::sync::atomic::Ordering::SeqCst);
+            let _ = app.emit("recognition-paused-changed", paused);
+            if let Err(e) = rebuild_menus(app) {
+                println!("⚠️ Failed to rebuild menus after pause toggle: {}", e);
+            }
+        }
+        "launch_at_login" => {
+            // Toggles the persisted preference; actual OS autostart
+            // registration lands with the launch-at-login feature work.
+            let state = app.state::<AppState>();
+            let enabled = !state.launch_at_login.load(std::sync::atomic::Ordering::SeqCst);
+            state.launch_at_login.store(enabled, std::sync::atomic::Ordering::SeqCst);
+            persist_ui_preferences(app);
+            if let Err(e) = rebuild_menus(app) {
+                println!("⚠️ Failed to rebuild menus after Launch at Login toggle: {}", e);
+            }
+        }
+        "show_on_all_workspaces" => {
+            let state = app.state::<AppState>();
+            let enabled = !state.show_on_all_workspaces.load(std::sync::atomic::Ordering::SeqCst);
+            state.show_on_all_workspaces.store(enabled, std::sync::atomic::Ordering::SeqCst);
+            persist_ui_preferences(app);
+
+            #[cfg(target_os = "macos")]
+            if let Some(window) = app.get_webview_window("main") {
+                apply_all_workspaces_preference(app, &window);
+            }
+
+            if let Err(e) = rebuild_menus(app) {
+                println!("⚠️ Failed to rebuild menus after Show on All Workspaces toggle: {}", e);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Record a freshly recognized gesture for the "Recently recognized" submenu
+/// and rebuild the menus so it shows up immediately.
+#[tauri::command]
+fn record_recognized_gesture(app: tauri::AppHandle, gesture: String) -> Result<(), String> {
+    {
+        let state = app.state::<AppState>();
+        let mut recent = state.recent_gestures.lock().unwrap();
+        recent.retain(|g| g != &gesture);
+        recent.insert(0, gesture);
+        recent.truncate(MAX_RECENT_GESTURES);
+    }
+    rebuild_menus(&app)
+}
+
+/// Reload the gesture-action registry from disk and rebuild both menus —
+/// call after editing the registry's config file so the running app picks it
+/// up without a restart.
+#[tauri::command]
+fn reload_action_registry(app: tauri::AppHandle) -> Result<(), String> {
+    let data = load_action_registry(&app.handle());
+    *app.state::<ActionRegistry>().0.lock().unwrap() = data;
+    rebuild_menus(&app)?;
+    let _ = app.emit("action-registry-changed", ());
+    Ok(())
 }
 
 // Function to periodically check and disable App Nap
@@ -605,13 +1538,73 @@ pub fn run() {
     }
 
     tauri::Builder::default()
+        // Must be the first plugin registered: routes a second launch to this
+        // running instance instead of letting it spawn its own window/tray.
+        .plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
+            println!("🔁 Second instance launched, focusing existing window");
+            show_window(app);
+            let _ = app.emit("single-instance-argv", argv);
+        }))
         .plugin(tauri_plugin_opener::init())
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(|app, _shortcut, event| {
+                    if event.state() == tauri_plugin_global_shortcut::ShortcutState::Pressed {
+                        if let Some(window) = app.get_webview_window("main") {
+                            if window.is_visible().unwrap_or(false) {
+                                hide_window(app);
+                            } else {
+                                show_window(app);
+                            }
+                        }
+                    }
+                })
+                .build(),
+        )
         .setup(|app| {
-            // Ensure the backend is running
-            if let Err(e) = ensure_backend_is_running() {
-                println!("❌ Failed to ensure backend is running: {}", e);
+            // Live purely in the tray: no dock icon, no entry in the app
+            // switcher, matching a menu-bar-agent app rather than a regular one.
+            #[cfg(target_os = "macos")]
+            app.set_activation_policy(tauri::ActivationPolicy::Accessory);
+
+            // Centralized app state: tray state lives here instead of a bare global
+            app.manage(AppState::new());
+            app.manage(TrayController::new());
+
+            // Restore persisted menu toggles (launch at login, show on all
+            // workspaces) before the tray/app menu is first built.
+            let ui_preferences = load_ui_preferences(&app.handle());
+            {
+                let state = app.state::<AppState>();
+                state.launch_at_login.store(ui_preferences.launch_at_login, std::sync::atomic::Ordering::SeqCst);
+                state.show_on_all_workspaces.store(ui_preferences.show_on_all_workspaces, std::sync::atomic::Ordering::SeqCst);
             }
-            
+
+            // Restore the persisted global shortcut (or the default) so the
+            // window-toggle hotkey survives restarts.
+            app.manage(GlobalShortcutState(Mutex::new(String::new())));
+            let saved_shortcut = load_global_shortcut(&app.handle());
+            if let Err(e) = register_global_shortcut(&app.handle(), &saved_shortcut) {
+                println!("⚠️ Failed to register global shortcut '{}': {}", saved_shortcut, e);
+            }
+
+            // Re-broadcast the current tray state whenever the frontend asks for it
+            // (e.g. right after it mounts and attaches its event listeners).
+            let state_handle = app.handle().clone();
+            app.listen("request-tray-state", move |_event| {
+                let state = state_handle.state::<AppState>().tray_state.lock().unwrap().clone();
+                let _ = state_handle.emit("tray-state-changed", TrayStateChangedPayload { state });
+            });
+
+            // Load the gesture-action registry and make it available to `run_action`
+            let action_registry = load_action_registry(&app.handle());
+            app.manage(ActionRegistry(Mutex::new(action_registry)));
+
+            // Start the backend supervisor: polls health, restarts with backoff,
+            // and drives the tray between `disconnected` and `ready`.
+            app.manage(BackendSupervisor::new());
+            start_backend_supervisor(app.handle().clone());
+
             // Remove any existing tray icons first to avoid duplicates
             if let Some(tray) = app.handle().tray_by_id("main") {
                 println!("🧹 Removing existing tray icon to avoid duplicates");
@@ -656,7 +1649,7 @@ pub fn run() {
             };
             
             // Create system tray with show_menu_on_left_click explicitly set to true for better visibility
-            let _tray = TrayIconBuilder::with_id("main")
+            let tray = TrayIconBuilder::with_id("main")
                 .tooltip("AirCut - Ready")
                 .icon(initial_icon)
                 .menu(&menu)
@@ -664,34 +1657,13 @@ pub fn run() {
                 .on_tray_icon_event(|tray, event| {
                     match event {
                         TrayIconEvent::Click { button: MouseButton::Left, .. } => {
-                            // Left click shows/hides the main window
+                            // Left click toggles the main window
                             let app = tray.app_handle();
                             if let Some(window) = app.get_webview_window("main") {
-                                // Check if window is visible (not off-screen)
-                                let is_visible = match window.outer_position() {
-                                    Ok(position) => position.x > -1000 && position.y > -1000,
-                                    Err(_) => false
-                                };
-                                
-                                if is_visible {
-                                    // Hide the window by moving it off-screen
-                                    println!("📱 Moving window off-screen");
-                                    let _ = window.set_position(tauri::LogicalPosition::new(-2000, -2000));
-                                    let _ = window.set_size(tauri::LogicalSize::new(1, 1));
+                                if window.is_visible().unwrap_or(false) {
+                                    hide_window(app);
                                 } else {
-                                    // Show the window by moving it back on-screen
-                                    println!("📱 Moving window back on-screen");
-                                    let _ = window.set_size(tauri::LogicalSize::new(1400, 1000));
-                                    let _ = window.center();
-                                    
-                                    // Ensure window is visible on all workspaces (all virtual desktops)
-                                    #[cfg(target_os = "macos")]
-                                    make_window_visible_on_all_workspaces(&window);
-                                    
-                                    let _ = window.set_focus();
-                                    
-                                    // Dispatch window-shown event
-                                    let _ = window.eval("window.dispatchEvent(new Event('window-shown'))");
+                                    show_window(app);
                                 }
                             }
                         }
@@ -702,66 +1674,54 @@ pub fn run() {
                     }
                 })
                 .on_menu_event(|app, event| {
-                    match event.id().as_ref() {
-                        "settings" => {
-                            // Show the main window when settings is clicked
-                            if let Some(window) = app.get_webview_window("main") {
-                                // Move the window back on-screen
-                                let _ = window.set_size(tauri::LogicalSize::new(1400, 1000));
-                                let _ = window.center();
-                                
-                                // Ensure window is visible on all workspaces (all virtual desktops)
-                                #[cfg(target_os = "macos")]
-                                make_window_visible_on_all_workspaces(&window);
-                                
-                                let _ = window.set_focus();
-                                
-                                // Dispatch window-shown event
-                                let _ = window.eval("window.dispatchEvent(new Event('window-shown'))");
-                            }
-                        }
-                        "quit" => {
-                            app.exit(0);
-                        }
-                        _ => {}
-                    }
+                    handle_shared_menu_event(app, event.id().as_ref());
                 })
                 .build(app)?;
-            
-            // Initialize the app even if window is hidden
+
+            app.state::<TrayController>().set_tray(tray);
+
+            // Native app menu bar, generated from the same gesture-action
+            // registry as the tray menu, and kept in sync via rebuild_menus.
+            let app_menu = create_app_menu(&app.handle())?;
+            app.set_menu(app_menu)?;
+            app.on_menu_event(|app, event| {
+                handle_shared_menu_event(app, event.id().as_ref());
+            });
+
+            // Initialize the app, then leave the window genuinely hidden
+            // (overlay titlebar on macOS, no decorations elsewhere, so it reads
+            // as a lightweight panel once shown).
             if let Some(window) = app.get_webview_window("main") {
-                // Ensure window is visible on all workspaces (all virtual desktops)
+                // On macOS keep the native close/minimize/zoom buttons but hide
+                // the title bar chrome behind them (decorum-style overlay
+                // titlebar) so the custom HTML titlebar can draw underneath;
+                // everywhere else there's no native title bar to overlay, so
+                // drop decorations entirely.
                 #[cfg(target_os = "macos")]
-                make_window_visible_on_all_workspaces(&window);
-                
+                apply_overlay_titlebar(&window);
+
+                #[cfg(not(target_os = "macos"))]
+                let _ = window.set_decorations(false);
+
+                #[cfg(target_os = "macos")]
+                apply_all_workspaces_preference(&app.handle(), &window);
+
                 // Show the window briefly to ensure initialization
                 let _ = window.show();
                 std::thread::sleep(std::time::Duration::from_millis(500));
-                
-                // Initialize the app
-                let app_handle = app.handle();
-                if let Some(main_window) = app_handle.get_webview_window("main") {
-                    let _ = main_window.eval("window.dispatchEvent(new Event('initialize-app'))");
-                }
-                
-                // Move window off-screen to keep it running but visually hidden
-                println!("📱 Moving window off-screen after initialization");
-                let _ = window.set_position(tauri::LogicalPosition::new(-2000, -2000));
-                let _ = window.set_size(tauri::LogicalSize::new(1, 1));
-                let _ = window.show(); // Keep it "visible" but off-screen
-                
+
+                let _ = window.emit("initialize-app", ());
+
+                println!("📱 Hiding window after initialization");
+                hide_window(&app.handle());
+
                 // Set up a handler for window close events
                 let app_handle = app.handle().clone();
                 window.on_window_event(move |event| {
                     if let tauri::WindowEvent::CloseRequested { api, .. } = event {
-                        // Prevent the window from closing
+                        // Prevent the window from closing; hide it instead
                         api.prevent_close();
-                        
-                        // Instead of closing the window, move it off-screen
-                        if let Some(window) = app_handle.get_webview_window("main") {
-                            let _ = window.set_position(tauri::LogicalPosition::new(-2000, -2000));
-                            let _ = window.set_size(tauri::LogicalSize::new(1, 1));
-                        }
+                        hide_window(&app_handle);
                     }
                 });
             }
@@ -784,27 +1744,145 @@ pub fn run() {
             
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![greet, execute_command, update_tray_icon, show_main_window, startup_complete])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .invoke_handler(tauri::generate_handler![
+            greet,
+            run_action,
+            reload_action_registry,
+            record_recognized_gesture,
+            set_global_shortcut,
+            #[cfg(debug_assertions)]
+            execute_command,
+            update_tray_icon,
+            show_main_window,
+            startup_complete,
+            get_backend_status,
+            restart_backend,
+            start_window_drag,
+            minimize_window,
+            maximize_window,
+            close_window
+        ])
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            // macOS: reactivating the app (e.g. opening it again from Finder;
+            // the Accessory policy leaves no dock icon or app switcher entry)
+            // while the window is genuinely hidden doesn't implicitly show it
+            // the way a minimized window would, so restore it through the same
+            // path as a tray left-click.
+            if let tauri::RunEvent::Reopen { has_visible_windows, .. } = event {
+                if !has_visible_windows {
+                    show_window(app);
+                }
+            }
+        });
+}
+
+/// Apply `make_window_visible_on_all_workspaces` only if the user has the
+/// "Show on All Workspaces" menu toggle enabled.
+#[cfg(target_os = "macos")]
+fn apply_all_workspaces_preference(app: &tauri::AppHandle, window: &tauri::WebviewWindow) {
+    if app.state::<AppState>().show_on_all_workspaces.load(std::sync::atomic::Ordering::SeqCst) {
+        make_window_visible_on_all_workspaces(window);
+    } else {
+        remove_window_visible_on_all_workspaces(window);
+    }
 }
 
 #[cfg(target_os = "macos")]
 fn make_window_visible_on_all_workspaces(window: &tauri::WebviewWindow) {
     use cocoa::appkit::{NSWindowCollectionBehavior};
     use objc::{msg_send, sel, sel_impl};
-    
+
     if let Ok(ns_window) = window.ns_window() {
         let ns_window = ns_window as cocoa::base::id;
-        
+
         unsafe {
             let current_behavior: NSWindowCollectionBehavior = msg_send![ns_window, collectionBehavior];
             let new_behavior = current_behavior | NSWindowCollectionBehavior::NSWindowCollectionBehaviorCanJoinAllSpaces;
             let _: () = msg_send![ns_window, setCollectionBehavior: new_behavior];
         }
-        
+
         println!("✅ Window set to be visible on all workspaces");
     } else {
         println!("⚠️ Failed to get NSWindow handle");
     }
 }
+
+/// Clear the all-spaces collection behavior bit, the inverse of
+/// `make_window_visible_on_all_workspaces`, so toggling "Show on All
+/// Workspaces" off actually takes it back.
+#[cfg(target_os = "macos")]
+fn remove_window_visible_on_all_workspaces(window: &tauri::WebviewWindow) {
+    use cocoa::appkit::{NSWindowCollectionBehavior};
+    use objc::{msg_send, sel, sel_impl};
+
+    if let Ok(ns_window) = window.ns_window() {
+        let ns_window = ns_window as cocoa::base::id;
+
+        unsafe {
+            let current_behavior: NSWindowCollectionBehavior = msg_send![ns_window, collectionBehavior];
+            let new_behavior = current_behavior & !NSWindowCollectionBehavior::NSWindowCollectionBehaviorCanJoinAllSpaces;
+            let _: () = msg_send![ns_window, setCollectionBehavior: new_behavior];
+        }
+
+        println!("✅ Window no longer visible on all workspaces");
+    } else {
+        println!("⚠️ Failed to get NSWindow handle");
+    }
+}
+
+/// Traffic-light inset from the window's top-left corner, matching the
+/// custom HTML titlebar's padding so the native buttons line up with it.
+#[cfg(target_os = "macos")]
+const TRAFFIC_LIGHT_INSET_X: f64 = 12.0;
+#[cfg(target_os = "macos")]
+const TRAFFIC_LIGHT_INSET_Y: f64 = 12.0;
+
+/// Hide the native title bar behind the content view (decorum-style overlay
+/// titlebar) and reposition the close/minimize/zoom buttons so they sit
+/// inset over the custom HTML titlebar instead of the system default spot,
+/// keeping the buttons native and clickable while removing the OS chrome.
+#[cfg(target_os = "macos")]
+fn apply_overlay_titlebar(window: &tauri::WebviewWindow) {
+    use cocoa::appkit::{NSWindow, NSWindowButton, NSWindowStyleMask, NSWindowTitleVisibility};
+    use cocoa::foundation::NSRect;
+    use objc::{msg_send, sel, sel_impl};
+
+    let Ok(ns_window) = window.ns_window() else {
+        println!("⚠️ Failed to get NSWindow handle for overlay titlebar");
+        return;
+    };
+    let ns_window = ns_window as cocoa::base::id;
+
+    unsafe {
+        ns_window.setTitlebarAppearsTransparent_(cocoa::base::YES);
+        ns_window.setTitleVisibility_(NSWindowTitleVisibility::NSWindowTitleHidden);
+
+        let style_mask = ns_window.styleMask() | NSWindowStyleMask::NSFullSizeContentViewWindowMask;
+        ns_window.setStyleMask_(style_mask);
+
+        let buttons = [
+            NSWindowButton::NSWindowCloseButton,
+            NSWindowButton::NSWindowMiniaturizeButton,
+            NSWindowButton::NSWindowZoomButton,
+        ];
+
+        let mut x = TRAFFIC_LIGHT_INSET_X;
+        for button_type in buttons {
+            let button: cocoa::base::id = msg_send![ns_window, standardWindowButton: button_type];
+            if button == cocoa::base::nil {
+                continue;
+            }
+
+            let frame: NSRect = msg_send![button, frame];
+            let superview: cocoa::base::id = msg_send![button, superview];
+            let superview_frame: NSRect = msg_send![superview, frame];
+            let y = superview_frame.size.height - TRAFFIC_LIGHT_INSET_Y - frame.size.height;
+            let _: () = msg_send![button, setFrameOrigin: cocoa::foundation::NSPoint::new(x, y)];
+            x += frame.size.width + 8.0;
+        }
+    }
+
+    println!("✅ Overlay titlebar applied, traffic lights repositioned");
+}